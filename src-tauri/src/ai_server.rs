@@ -1,38 +1,149 @@
-use std::sync::Mutex;
-use tauri::{AppHandle, Manager};
+use futures_util::StreamExt;
 use serde::{Deserialize, Serialize};
-use std::process::{Child, Command, Stdio};
+use std::collections::{HashMap, VecDeque};
+use std::net::TcpListener;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, AtomicU16, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::process::{Child, Command};
+use tokio::sync::Mutex as AsyncMutex;
+use tokio::sync::{mpsc, Notify, Semaphore};
+use uuid::Uuid;
+
+/// Default time allowed for the sidecar to report a healthy `/api/health` before giving up.
+const DEFAULT_STARTUP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Number of most recent stdout/stderr lines kept in memory for the diagnostics panel.
+const LOG_BUFFER_CAPACITY: usize = 500;
+
+/// How often the supervisor checks whether the sidecar process is still alive.
+const SUPERVISOR_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Give up auto-restarting after this many consecutive restarts (i.e. on the next
+/// unexpected exit after them), so a sidecar that keeps crashing doesn't spin forever.
+const MAX_CONSECUTIVE_RESTARTS: u32 = 5;
+
+/// Number of inference jobs allowed to run against the sidecar at once, across all
+/// models. Bounds how many files a batch can push through the sidecar concurrently, so
+/// a large batch can't starve an interactive single-file job or overload the sidecar's
+/// CPU/GPU.
+const MAX_CONCURRENT_INFERENCES: usize = 2;
+
+/// Number of workers pulling from the `queue_detection` job queue at once. Kept at 1 so
+/// a queued batch runs strictly one-at-a-time instead of fanning out, unlike
+/// `run_inference` callers racing each other for `MAX_CONCURRENT_INFERENCES` permits.
+const DEFAULT_QUEUE_WORKERS: usize = 1;
+
+/// A single line of sidecar output, surfaced to the frontend via the `ai-server-log` event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogLine {
+    pub stream: String,
+    pub line: String,
+    pub timestamp: i64,
+}
 
 /// AI server state
 pub struct AIServerState {
     pub process: Mutex<Option<Child>>,
-    pub port: u16,
+    /// Ephemeral port the sidecar is bound to. Resolved fresh on every `start_ai_server`
+    /// call, so it's an atomic rather than a fixed constant.
+    pub port: AtomicU16,
+    /// How long `start_ai_server` polls for readiness before failing. Kept as a field
+    /// (rather than a constant) so it can be shortened for tests.
+    pub startup_timeout: Duration,
+    /// Ring buffer of the most recent sidecar stdout/stderr lines.
+    pub logs: Mutex<VecDeque<LogLine>>,
+    /// Set while `stop_ai_server` is tearing the process down, so the supervisor task
+    /// that notices the exit doesn't treat it as a crash and restart it.
+    pub stopping: AtomicBool,
+    /// Consecutive unexpected exits since the last start that was not a supervisor
+    /// auto-restart, used to cap auto-restart attempts.
+    pub restart_attempts: AtomicU32,
+    /// Cancellation handles for in-flight inference jobs, keyed by the job id the
+    /// caller supplied to `run_inference`.
+    pub active_inferences: Mutex<HashMap<String, Arc<Notify>>>,
+    /// Limits how many inference jobs run against the sidecar at once; acquired by
+    /// every `run_inference` call, so a batch queues past `MAX_CONCURRENT_INFERENCES`.
+    pub inference_semaphore: Semaphore,
+    /// Bearer token the sidecar requires on every request. Regenerated each time
+    /// `start_ai_server` spawns a fresh process, so a token leaked from a previous run
+    /// (or another local process guessing the old hardcoded port) can't be replayed.
+    pub secret: Mutex<String>,
+    /// Lifecycle state tracked by `spawn_supervisor`, independent of the live
+    /// `check_server_health` probe `get_server_status` runs - this is what lets a
+    /// frontend tell "down because of a transient restart" from "down for good".
+    pub health: Mutex<ServerHealth>,
+    /// Record of every job submitted via `queue_detection`, keyed by job id and kept
+    /// after completion so `get_queue_status` can report individual failures.
+    pub jobs: Mutex<HashMap<String, DetectionJob>>,
+    /// Sender half of the detection job queue; `None` until `job_queue_sender` lazily
+    /// starts the worker pool on the first `queue_detection` call.
+    job_queue_tx: Mutex<Option<mpsc::UnboundedSender<QueuedJob>>>,
 }
 
 impl AIServerState {
     pub fn new() -> Self {
         Self {
             process: Mutex::new(None),
-            port: 8000,
+            port: AtomicU16::new(0),
+            startup_timeout: DEFAULT_STARTUP_TIMEOUT,
+            logs: Mutex::new(VecDeque::with_capacity(LOG_BUFFER_CAPACITY)),
+            stopping: AtomicBool::new(false),
+            restart_attempts: AtomicU32::new(0),
+            active_inferences: Mutex::new(HashMap::new()),
+            inference_semaphore: Semaphore::new(MAX_CONCURRENT_INFERENCES),
+            secret: Mutex::new(String::new()),
+            health: Mutex::new(ServerHealth::Stopped),
+            jobs: Mutex::new(HashMap::new()),
+            job_queue_tx: Mutex::new(None),
         }
     }
 }
 
+/// Lifecycle state of the sidecar, tracked by `spawn_supervisor` and surfaced via
+/// `ServerStatus::health` so the frontend can tell a supervised restart-in-progress
+/// from a sidecar that has given up for good.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ServerHealth {
+    /// No start has been attempted yet, or `stop_ai_server` tore the process down on
+    /// purpose.
+    Stopped,
+    /// The sidecar is up and its last startup health check passed.
+    Running,
+    /// The sidecar exited unexpectedly and the supervisor is attempting an auto-restart.
+    Restarting,
+    /// The sidecar exited unexpectedly again after `MAX_CONSECUTIVE_RESTARTS` auto-restarts
+    /// in a row (or a start attempt, manual or automatic, failed), so it is not being
+    /// restarted.
+    Crashed,
+}
+
+/// Payload for the `ai-server-crashed` event, emitted whenever the supervisor notices
+/// the sidecar exited without `stop_ai_server` having been called.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerCrashed {
+    pub exit_status: String,
+    pub stderr_tail: String,
+    /// Whether the supervisor is about to attempt an auto-restart, or has given up.
+    pub restarting: bool,
+    pub attempt: u32,
+    pub max_attempts: u32,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ServerStatus {
     pub running: bool,
     pub port: u16,
     pub version: String,
+    pub health: ServerHealth,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct VertebraeResult {
-    pub success: bool,
-    pub vertebrae: Vec<Vertebra>,
-    pub processing_time_ms: f64,
-    pub error: Option<String>,
-}
-
+/// Shape of a vertebrae-detection model's output, carried in `InferenceResult::output`
+/// when `model == "vertebrae"`.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Vertebra {
     pub label: String,
@@ -47,6 +158,56 @@ pub struct Point3D {
     pub z: f64,
 }
 
+/// Result of a `run_inference` call. `output` holds whatever shape the requested
+/// `model` produces (e.g. a `Vec<Vertebra>` for `model == "vertebrae"`) so adding a new
+/// model doesn't require a new Tauri command or result type.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InferenceResult {
+    pub success: bool,
+    pub model: String,
+    pub output: serde_json::Value,
+    pub processing_time_ms: f64,
+    pub error: Option<String>,
+}
+
+/// Result of `detect_vertebrae`, kept in its original pre-`run_inference` shape so
+/// existing callers checking `.success`/`.error` don't break.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VertebraeResult {
+    pub success: bool,
+    pub vertebrae: Vec<Vertebra>,
+    pub processing_time_ms: f64,
+    pub error: Option<String>,
+}
+
+/// Progress update for an in-flight inference job, surfaced to the frontend via the
+/// `inference-progress` event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InferenceProgress {
+    pub job_id: String,
+    pub model: String,
+    pub percent: f64,
+    pub message: String,
+}
+
+/// One line of the sidecar's newline-delimited progress stream for an inference job.
+/// The `Complete` payload omits `model` - the caller already knows which model it asked
+/// for, so `run_inference_stream` fills that field in on `InferenceResult` itself.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum InferenceEvent {
+    Progress {
+        percent: f64,
+        message: String,
+    },
+    Complete {
+        success: bool,
+        output: serde_json::Value,
+        processing_time_ms: f64,
+        error: Option<String>,
+    },
+}
+
 /// Start the AI inference server sidecar
 #[tauri::command]
 pub async fn start_ai_server(app: AppHandle) -> Result<ServerStatus, String> {
@@ -58,67 +219,349 @@ pub async fn start_ai_server(app: AppHandle) -> Result<ServerStatus, String> {
         if process_lock.is_some() {
             return Ok(ServerStatus {
                 running: true,
-                port: state.port,
+                port: state.port.load(Ordering::SeqCst),
                 version: "1.0.0".to_string(),
+                health: *state.health.lock().unwrap(),
             });
         }
     }
 
+    state.stopping.store(false, Ordering::SeqCst);
+
     // Get the sidecar path
     let sidecar_path = app
         .path()
         .resolve("openscans-inference", tauri::path::BaseDirectory::Resource)
         .map_err(|e| format!("Failed to resolve sidecar path: {}", e))?;
 
-    log::info!("[AI Server] Starting sidecar at: {:?}", sidecar_path);
+    // Let the OS pick a free ephemeral port instead of a hardcoded one, so a second
+    // instance of the app (or any other process already bound to the old port) can't
+    // collide with this one.
+    let port = allocate_free_port().map_err(|e| format!("Failed to allocate a port: {}", e))?;
+    state.port.store(port, Ordering::SeqCst);
+
+    // Generate a fresh shared secret for this sidecar process and require it on every
+    // request, so another local process can't hit the port we just bound.
+    let secret = Uuid::new_v4().to_string();
+    *state.secret.lock().unwrap() = secret.clone();
+
+    log::info!(
+        "[AI Server] Starting sidecar at: {:?} on port {}",
+        sidecar_path,
+        port
+    );
 
     // Start the sidecar process
-    let child = Command::new(sidecar_path)
+    // Passed via env rather than argv: process argv is world-readable (`ps`,
+    // `/proc/<pid>/cmdline`), while `/proc/<pid>/environ` is restricted to the owning
+    // user, and leaking the token to another local process is exactly what it guards
+    // against.
+    let mut child = Command::new(sidecar_path)
+        .arg("--port")
+        .arg(port.to_string())
+        .env("AUTH_TOKEN", &secret)
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .spawn()
         .map_err(|e| format!("Failed to spawn sidecar: {}", e))?;
 
-    log::info!("[AI Server] Sidecar process started with PID: {}", child.id());
+    log::info!(
+        "[AI Server] Sidecar process started with PID: {:?}",
+        child.id()
+    );
 
-    // Store the child process
-    {
-        let mut process_lock = state.process.lock().unwrap();
-        *process_lock = Some(child);
+    // Stream stdout/stderr line-by-line instead of leaving them unread, which would
+    // otherwise risk filling the OS pipe buffer and deadlocking a chatty sidecar.
+    if let Some(stdout) = child.stdout.take() {
+        spawn_log_reader(app.clone(), "stdout", stdout);
+    }
+    if let Some(stderr) = child.stderr.take() {
+        spawn_log_reader(app.clone(), "stderr", stderr);
     }
 
-    // Wait for server to start (Python+FastAPI takes ~10 seconds)
-    tokio::time::sleep(tokio::time::Duration::from_secs(10)).await;
+    // Poll for readiness instead of blindly sleeping, so we return as soon as the
+    // sidecar is actually up and surface the real crash reason if it isn't.
+    let ready =
+        wait_for_server_ready(&mut child, &app, port, &secret, state.startup_timeout).await;
 
-    // Check if server is responding
-    match check_server_health(state.port).await {
+    match ready {
         Ok(_) => {
             log::info!("[AI Server] Health check passed");
+            {
+                let mut process_lock = state.process.lock().unwrap();
+                *process_lock = Some(child);
+            }
+            state.restart_attempts.store(0, Ordering::SeqCst);
+            *state.health.lock().unwrap() = ServerHealth::Running;
+            spawn_supervisor(app.clone());
             Ok(ServerStatus {
                 running: true,
-                port: state.port,
+                port,
                 version: "1.0.0".to_string(),
+                health: ServerHealth::Running,
             })
         }
         Err(e) => {
             log::error!("[AI Server] Health check failed: {}", e);
+            *state.health.lock().unwrap() = ServerHealth::Crashed;
+            // Never healthy, so don't leave it in `state.process` - a hung child
+            // parked there would make the "already running" guard above report
+            // `running: true` forever and block any later retry.
+            let _ = child.kill().await;
             Err(format!("Server started but health check failed: {}", e))
         }
     }
 }
 
+/// Watch the running sidecar and restart it if it exits without `stop_ai_server` having
+/// been called, so a crash doesn't silently leave inference unavailable. Gives up after
+/// `MAX_CONSECUTIVE_RESTARTS` restarts in a row, since a sidecar that can't stay up needs a
+/// human to look at it rather than more restarts.
+fn spawn_supervisor(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(SUPERVISOR_POLL_INTERVAL).await;
+
+            let state = app.state::<AIServerState>();
+            let exit_status = {
+                let mut process_lock = state.process.lock().unwrap();
+                match process_lock.as_mut() {
+                    Some(child) => match child.try_wait() {
+                        Ok(Some(status)) => {
+                            *process_lock = None;
+                            Some(status)
+                        }
+                        Ok(None) => None,
+                        Err(e) => {
+                            log::error!("[AI Server] failed to poll sidecar status: {}", e);
+                            return;
+                        }
+                    },
+                    // Either never started or already torn down (manual stop) - nothing
+                    // left for this supervisor to watch.
+                    None => return,
+                }
+            };
+
+            let Some(status) = exit_status else {
+                continue;
+            };
+
+            if state.stopping.load(Ordering::SeqCst) {
+                log::info!("[AI Server] Sidecar stopped ({})", status);
+                return;
+            }
+
+            let attempts = state.restart_attempts.fetch_add(1, Ordering::SeqCst) + 1;
+            let will_retry = attempts <= MAX_CONSECUTIVE_RESTARTS;
+            let stderr_tail = recent_log_lines(&app, "stderr", 20).join("\n");
+
+            *state.health.lock().unwrap() = if will_retry {
+                ServerHealth::Restarting
+            } else {
+                ServerHealth::Crashed
+            };
+
+            if let Err(e) = app.emit(
+                "ai-server-crashed",
+                ServerCrashed {
+                    exit_status: status.to_string(),
+                    stderr_tail,
+                    restarting: will_retry,
+                    attempt: attempts,
+                    max_attempts: MAX_CONSECUTIVE_RESTARTS,
+                },
+            ) {
+                log::warn!("[AI Server] failed to emit ai-server-crashed event: {}", e);
+            }
+
+            if !will_retry {
+                log::error!(
+                    "[AI Server] Sidecar exited unexpectedly ({}) and gave up after {} consecutive restarts",
+                    status,
+                    MAX_CONSECUTIVE_RESTARTS
+                );
+                return;
+            }
+
+            log::warn!(
+                "[AI Server] Sidecar exited unexpectedly ({}), restarting (attempt {}/{})",
+                status,
+                attempts,
+                MAX_CONSECUTIVE_RESTARTS
+            );
+
+            match start_ai_server(app.clone()).await {
+                // start_ai_server resets the counter on success; carry this run's count
+                // over so exits after each auto-restart keep adding up toward the cap.
+                Ok(_) => state.restart_attempts.store(attempts, Ordering::SeqCst),
+                Err(e) => {
+                    log::error!("[AI Server] Auto-restart failed: {}", e);
+                    *state.health.lock().unwrap() = ServerHealth::Crashed;
+                }
+            }
+
+            // start_ai_server spawns its own supervisor on success (and sets `health` to
+            // either `Running` or `Crashed` either way), so this instance's watch loop is
+            // done regardless of outcome.
+            return;
+        }
+    });
+}
+
+/// Bind to an OS-assigned ephemeral port and return it, releasing the listener so the
+/// sidecar can bind it in turn. There's a small window where another process could
+/// steal the port before the sidecar starts, but this is the standard "ask the OS"
+/// approach and good enough for a local-only sidecar.
+fn allocate_free_port() -> std::io::Result<u16> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    listener.local_addr().map(|addr| addr.port())
+}
+
+/// Poll `/api/health`, starting every 250ms and backing off exponentially up to every 2s,
+/// until it responds, the child exits, or `timeout` elapses. Returns the captured stderr tail if the
+/// child exits during startup, so the caller can surface the real crash reason.
+async fn wait_for_server_ready(
+    child: &mut Child,
+    app: &AppHandle,
+    port: u16,
+    secret: &str,
+    timeout: Duration,
+) -> Result<(), String> {
+    let deadline = Instant::now() + timeout;
+    let mut interval = Duration::from_millis(250);
+    let max_interval = Duration::from_secs(2);
+
+    loop {
+        if check_server_health(port, secret).await.is_ok() {
+            return Ok(());
+        }
+
+        if let Ok(Some(status)) = child.try_wait() {
+            let stderr_tail = recent_log_lines(app, "stderr", 20).join("\n");
+            return Err(format!(
+                "sidecar exited during startup ({}): {}",
+                status,
+                if stderr_tail.trim().is_empty() {
+                    "no output captured"
+                } else {
+                    stderr_tail.trim()
+                }
+            ));
+        }
+
+        if Instant::now() >= deadline {
+            return Err(format!(
+                "timed out waiting for /api/health after {:?}",
+                timeout
+            ));
+        }
+
+        tokio::time::sleep(interval).await;
+        interval = (interval * 2).min(max_interval);
+    }
+}
+
+/// Read a single child output stream line-by-line, forwarding each line to the `log`
+/// crate, the ring buffer in `AIServerState`, and the `ai-server-log` Tauri event.
+fn spawn_log_reader<R>(app: AppHandle, stream: &'static str, reader: R)
+where
+    R: AsyncRead + Unpin + Send + 'static,
+{
+    tauri::async_runtime::spawn(async move {
+        let mut lines = BufReader::new(reader).lines();
+        loop {
+            match lines.next_line().await {
+                Ok(Some(line)) => {
+                    match stream {
+                        "stderr" => log::warn!("[AI Server] {}", line),
+                        _ => log::info!("[AI Server] {}", line),
+                    }
+                    push_log_line(&app, stream, line);
+                }
+                Ok(None) => break,
+                Err(e) => {
+                    log::error!("[AI Server] failed to read {} from sidecar: {}", stream, e);
+                    break;
+                }
+            }
+        }
+    });
+}
+
+/// Append a line to the ring buffer and emit it as an `ai-server-log` event.
+fn push_log_line(app: &AppHandle, stream: &str, line: String) {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0);
+    let entry = LogLine {
+        stream: stream.to_string(),
+        line,
+        timestamp,
+    };
+
+    {
+        let state = app.state::<AIServerState>();
+        let mut logs = state.logs.lock().unwrap();
+        if logs.len() >= LOG_BUFFER_CAPACITY {
+            logs.pop_front();
+        }
+        logs.push_back(entry.clone());
+    }
+
+    if let Err(e) = app.emit("ai-server-log", entry) {
+        log::warn!("[AI Server] failed to emit ai-server-log event: {}", e);
+    }
+}
+
+/// Return up to `count` most recent lines from a given stream, oldest first.
+fn recent_log_lines(app: &AppHandle, stream: &str, count: usize) -> Vec<String> {
+    let state = app.state::<AIServerState>();
+    let logs = state.logs.lock().unwrap();
+    logs.iter()
+        .rev()
+        .filter(|l| l.stream == stream)
+        .take(count)
+        .map(|l| l.line.clone())
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .collect()
+}
+
+/// Get recent sidecar stdout/stderr lines for a diagnostics panel
+#[tauri::command]
+pub async fn get_server_logs(app: AppHandle) -> Result<Vec<LogLine>, String> {
+    let state = app.state::<AIServerState>();
+    let logs = state.logs.lock().unwrap();
+    Ok(logs.iter().cloned().collect())
+}
+
 /// Stop the AI inference server
 #[tauri::command]
 pub async fn stop_ai_server(app: AppHandle) -> Result<(), String> {
     let state = app.state::<AIServerState>();
 
-    let mut process_lock = state.process.lock().unwrap();
+    // Tell the supervisor this exit is intentional before it can observe it, so it
+    // doesn't try to restart the sidecar out from under us.
+    state.stopping.store(true, Ordering::SeqCst);
+
+    let child = {
+        let mut process_lock = state.process.lock().unwrap();
+        process_lock.take()
+    };
 
-    if let Some(mut child) = process_lock.take() {
-        child.kill().map_err(|e| format!("Failed to kill process: {}", e))?;
+    if let Some(mut child) = child {
+        child
+            .kill()
+            .await
+            .map_err(|e| format!("Failed to kill process: {}", e))?;
         log::info!("[AI Server] Stopped");
     }
 
+    *state.health.lock().unwrap() = ServerHealth::Stopped;
+
     Ok(())
 }
 
@@ -132,56 +575,343 @@ pub async fn get_server_status(app: AppHandle) -> Result<ServerStatus, String> {
         process_lock.is_some()
     }; // Lock is dropped here
 
+    let port = state.port.load(Ordering::SeqCst);
+    let secret = state.secret.lock().unwrap().clone();
+    let health = *state.health.lock().unwrap();
+
     if running {
         // Verify server is actually responding
-        match check_server_health(state.port).await {
+        match check_server_health(port, &secret).await {
             Ok(_) => Ok(ServerStatus {
                 running: true,
-                port: state.port,
+                port,
                 version: "1.0.0".to_string(),
+                health,
             }),
             Err(_) => Ok(ServerStatus {
                 running: false,
-                port: state.port,
+                port,
                 version: "1.0.0".to_string(),
+                health,
             }),
         }
     } else {
         Ok(ServerStatus {
             running: false,
-            port: state.port,
+            port,
             version: "1.0.0".to_string(),
+            health,
         })
     }
 }
 
-/// Detect vertebrae in a DICOM file
+/// Run a named inference model (e.g. `"vertebrae"`) over a DICOM file. Inference can
+/// take a while, so the sidecar streams progress as newline-delimited JSON instead of
+/// returning a single response; each progress line is forwarded to the frontend as an
+/// `inference-progress` event, keyed by `job_id` so a UI tracking multiple jobs can
+/// tell them apart.
+///
+/// `job_id` is chosen by the caller (rather than generated here) so it can be passed to
+/// `cancel_inference` before this command resolves.
+#[tauri::command]
+pub async fn run_inference(
+    app: AppHandle,
+    model: String,
+    file_path: String,
+    job_id: String,
+) -> Result<InferenceResult, String> {
+    let state = app.state::<AIServerState>();
+
+    // Ensure server is running
+    {
+        let process_lock = state.process.lock().unwrap();
+        if process_lock.is_none() {
+            return Err("AI server is not running".to_string());
+        }
+    }
+
+    let cancel = Arc::new(Notify::new());
+    {
+        let mut active = state.active_inferences.lock().unwrap();
+        active.insert(job_id.clone(), cancel.clone());
+    }
+
+    let result = run_inference_stream(&app, &job_id, &model, &file_path, &cancel).await;
+
+    {
+        let mut active = state.active_inferences.lock().unwrap();
+        active.remove(&job_id);
+    }
+
+    result
+}
+
+/// Backward-compatible wrapper over `run_inference` for the vertebrae detection model,
+/// preserving the original `VertebraeResult` shape so existing callers don't have to
+/// change how they read `success`/`error`/`vertebrae`.
 #[tauri::command]
 pub async fn detect_vertebrae(
     app: AppHandle,
     file_path: String,
+    job_id: String,
 ) -> Result<VertebraeResult, String> {
+    let result = run_inference(app, "vertebrae".to_string(), file_path, job_id).await?;
+
+    // `success: false` is a normal outcome on a 200 response (e.g. "no vertebrae
+    // found"), not an `Err` - don't try to parse `output` (likely absent) in that case,
+    // and surface the sidecar's own error message instead of a parse failure.
+    if !result.success {
+        return Ok(VertebraeResult {
+            success: false,
+            vertebrae: Vec::new(),
+            processing_time_ms: result.processing_time_ms,
+            error: result.error,
+        });
+    }
+
+    let vertebrae: Vec<Vertebra> = serde_json::from_value(result.output)
+        .map_err(|e| format!("Failed to parse vertebrae detection output: {}", e))?;
+
+    Ok(VertebraeResult {
+        success: true,
+        vertebrae,
+        processing_time_ms: result.processing_time_ms,
+        error: result.error,
+    })
+}
+
+/// Cancel an in-progress `run_inference` job. A no-op error if the job already
+/// finished or never existed, since the caller may race the job's own completion.
+#[tauri::command]
+pub async fn cancel_inference(app: AppHandle, job_id: String) -> Result<(), String> {
     let state = app.state::<AIServerState>();
 
-    // Ensure server is running
+    let cancel = {
+        let active = state.active_inferences.lock().unwrap();
+        active.get(&job_id).cloned()
+    };
+
+    match cancel {
+        Some(cancel) => {
+            cancel.notify_one();
+            Ok(())
+        }
+        None => Err(format!("no in-progress inference with job id {}", job_id)),
+    }
+}
+
+/// A job submitted to the detection queue, in the order `queue_detection` accepts it.
+struct QueuedJob {
+    job_id: String,
+    model: String,
+    file_path: String,
+}
+
+/// Where a `queue_detection` job is in its lifecycle. Transitions are one-way:
+/// `Queued` -> `Running` -> `Done`/`Failed`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum DetectionJobState {
+    Queued,
+    Running,
+    Done { result: InferenceResult },
+    Failed { error: String },
+}
+
+/// A job's current record in `AIServerState::jobs`, returned by `get_queue_status` and
+/// emitted on every transition via the `detection-job-updated` event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectionJob {
+    pub job_id: String,
+    pub model: String,
+    pub file_path: String,
+    pub state: DetectionJobState,
+}
+
+/// Submit a detection job to the shared queue instead of running it inline. Jobs are
+/// processed FIFO by a fixed-size worker pool (`DEFAULT_QUEUE_WORKERS`, default 1), so a
+/// large batch is throttled at the front door rather than fanned out like
+/// `run_inference` callers racing each other for `MAX_CONCURRENT_INFERENCES` permits.
+/// The job's `DetectionJob` record is kept in `get_queue_status` after it finishes, so a
+/// caller can find and resubmit an individual failure instead of the whole batch.
+#[tauri::command]
+pub async fn queue_detection(
+    app: AppHandle,
+    job_id: String,
+    model: String,
+    file_path: String,
+) -> Result<(), String> {
     {
+        let state = app.state::<AIServerState>();
         let process_lock = state.process.lock().unwrap();
         if process_lock.is_none() {
             return Err("AI server is not running".to_string());
         }
     }
 
-    // Call the Python API
+    {
+        let state = app.state::<AIServerState>();
+        let mut jobs = state.jobs.lock().unwrap();
+        jobs.insert(
+            job_id.clone(),
+            DetectionJob {
+                job_id: job_id.clone(),
+                model: model.clone(),
+                file_path: file_path.clone(),
+                state: DetectionJobState::Queued,
+            },
+        );
+    }
+    emit_job_updated(&app, &job_id);
+
+    job_queue_sender(&app)
+        .send(QueuedJob {
+            job_id,
+            model,
+            file_path,
+        })
+        .map_err(|_| "job queue worker pool is not running".to_string())
+}
+
+/// Current record of every job submitted via `queue_detection`, including finished
+/// ones, sorted by job id for a stable order across calls.
+#[tauri::command]
+pub async fn get_queue_status(app: AppHandle) -> Result<Vec<DetectionJob>, String> {
+    let state = app.state::<AIServerState>();
+    let jobs = state.jobs.lock().unwrap();
+    let mut jobs: Vec<DetectionJob> = jobs.values().cloned().collect();
+    jobs.sort_by(|a, b| a.job_id.cmp(&b.job_id));
+    Ok(jobs)
+}
+
+/// Return the queue's job sender, lazily starting `DEFAULT_QUEUE_WORKERS` worker tasks
+/// the first time a job is queued. Workers share one receiver behind an async `Mutex`
+/// rather than each getting their own channel, so `DEFAULT_QUEUE_WORKERS` is the actual
+/// cap on in-flight queued jobs regardless of how many are submitted at once.
+fn job_queue_sender(app: &AppHandle) -> mpsc::UnboundedSender<QueuedJob> {
+    let state = app.state::<AIServerState>();
+    let mut tx_lock = state.job_queue_tx.lock().unwrap();
+    if let Some(tx) = tx_lock.as_ref() {
+        return tx.clone();
+    }
+
+    let (tx, rx) = mpsc::unbounded_channel();
+    *tx_lock = Some(tx.clone());
+
+    let rx = Arc::new(AsyncMutex::new(rx));
+    for _ in 0..DEFAULT_QUEUE_WORKERS {
+        spawn_queue_worker(app.clone(), rx.clone());
+    }
+
+    tx
+}
+
+/// Pull jobs off the shared queue receiver one at a time and run them to completion,
+/// so at most one job from this worker is `Running` at once.
+fn spawn_queue_worker(app: AppHandle, rx: Arc<AsyncMutex<mpsc::UnboundedReceiver<QueuedJob>>>) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let job = rx.lock().await.recv().await;
+            let Some(job) = job else {
+                // Sender dropped - nothing left to do.
+                return;
+            };
+
+            run_queued_job(&app, job).await;
+        }
+    });
+}
+
+/// Run one queued job through `run_inference`, recording its `Running` ->
+/// `Done`/`Failed` transition. Reuses `run_inference` (rather than calling
+/// `run_inference_stream` directly) so a queued job still gets its own
+/// `active_inferences` entry and can be cancelled via `cancel_inference` once running.
+async fn run_queued_job(app: &AppHandle, job: QueuedJob) {
+    let QueuedJob {
+        job_id,
+        model,
+        file_path,
+    } = job;
+
+    update_job_state(app, &job_id, DetectionJobState::Running);
+
+    let state = match run_inference(app.clone(), model, file_path, job_id.clone()).await {
+        Ok(result) => DetectionJobState::Done { result },
+        Err(error) => DetectionJobState::Failed { error },
+    };
+
+    update_job_state(app, &job_id, state);
+}
+
+/// Update a job's recorded state and emit `detection-job-updated`, so a UI watching the
+/// queue doesn't have to poll `get_queue_status`.
+fn update_job_state(app: &AppHandle, job_id: &str, new_state: DetectionJobState) {
+    {
+        let state = app.state::<AIServerState>();
+        let mut jobs = state.jobs.lock().unwrap();
+        match jobs.get_mut(job_id) {
+            Some(job) => job.state = new_state,
+            // Job was never recorded (shouldn't happen outside tests poking the
+            // queue directly) - nothing to update or emit.
+            None => return,
+        }
+    }
+    emit_job_updated(app, job_id);
+}
+
+fn emit_job_updated(app: &AppHandle, job_id: &str) {
+    let job = {
+        let state = app.state::<AIServerState>();
+        let jobs = state.jobs.lock().unwrap();
+        jobs.get(job_id).cloned()
+    };
+
+    if let Some(job) = job {
+        if let Err(e) = app.emit("detection-job-updated", job) {
+            log::warn!("[AI Server] failed to emit detection-job-updated event: {}", e);
+        }
+    }
+}
+
+/// Call the sidecar's streaming inference endpoint and drive it to completion,
+/// emitting an `inference-progress` event per progress line and returning as soon as
+/// either a result arrives or `cancel` is notified.
+async fn run_inference_stream(
+    app: &AppHandle,
+    job_id: &str,
+    model: &str,
+    file_path: &str,
+    cancel: &Notify,
+) -> Result<InferenceResult, String> {
+    let state = app.state::<AIServerState>();
+
+    // Wait for a free inference slot before touching the sidecar at all, so queued
+    // batch items can still be cancelled before they ever start.
+    let _permit = tokio::select! {
+        biased;
+        _ = cancel.notified() => {
+            return Err("inference cancelled".to_string());
+        }
+        permit = state.inference_semaphore.acquire() => {
+            permit.map_err(|e| format!("Failed to acquire an inference slot: {}", e))?
+        }
+    };
+
+    let port = state.port.load(Ordering::SeqCst);
+    let secret = state.secret.lock().unwrap().clone();
+
     let client = reqwest::Client::new();
-    let url = format!("http://127.0.0.1:{}/api/detect-vertebrae", state.port);
+    let url = format!("http://127.0.0.1:{}/api/inference/stream", port);
 
     let payload = serde_json::json!({
+        "model": model,
         "file_path": file_path,
         "fast_mode": true,
     });
 
     let response = client
         .post(&url)
+        .bearer_auth(&secret)
         .json(&payload)
         .send()
         .await
@@ -189,25 +919,80 @@ pub async fn detect_vertebrae(
 
     if !response.status().is_success() {
         let status = response.status();
-        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
         return Err(format!("API error ({}): {}", status, error_text));
     }
 
-    let result: VertebraeResult = response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse response: {}", e))?;
+    let mut stream = response.bytes_stream();
+    let mut buf: Vec<u8> = Vec::new();
+
+    loop {
+        let chunk = tokio::select! {
+            biased;
+            _ = cancel.notified() => {
+                return Err("inference cancelled".to_string());
+            }
+            chunk = stream.next() => chunk,
+        };
+
+        let Some(chunk) = chunk else {
+            return Err("sidecar closed the connection before sending a result".to_string());
+        };
+        let chunk = chunk.map_err(|e| format!("Failed to read response: {}", e))?;
+        buf.extend_from_slice(&chunk);
 
-    Ok(result)
+        while let Some(pos) = buf.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = buf.drain(..=pos).collect();
+            let line = line.strip_suffix(b"\n").unwrap_or(&line);
+            if line.is_empty() {
+                continue;
+            }
+
+            let event: InferenceEvent = serde_json::from_slice(line)
+                .map_err(|e| format!("Failed to parse inference event: {}", e))?;
+
+            match event {
+                InferenceEvent::Progress { percent, message } => {
+                    let _ = app.emit(
+                        "inference-progress",
+                        InferenceProgress {
+                            job_id: job_id.to_string(),
+                            model: model.to_string(),
+                            percent,
+                            message,
+                        },
+                    );
+                }
+                InferenceEvent::Complete {
+                    success,
+                    output,
+                    processing_time_ms,
+                    error,
+                } => {
+                    return Ok(InferenceResult {
+                        success,
+                        model: model.to_string(),
+                        output,
+                        processing_time_ms,
+                        error,
+                    })
+                }
+            }
+        }
+    }
 }
 
 /// Check if the AI server is healthy
-async fn check_server_health(port: u16) -> Result<(), String> {
+async fn check_server_health(port: u16, secret: &str) -> Result<(), String> {
     let client = reqwest::Client::new();
     let url = format!("http://127.0.0.1:{}/api/health", port);
 
     let response = client
         .get(&url)
+        .bearer_auth(secret)
         .timeout(std::time::Duration::from_secs(2))
         .send()
         .await
@@ -216,6 +1001,89 @@ async fn check_server_health(port: u16) -> Result<(), String> {
     if response.status().is_success() {
         Ok(())
     } else {
-        Err(format!("Health check returned status: {}", response.status()))
+        Err(format!(
+            "Health check returned status: {}",
+            response.status()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_app() -> tauri::App<tauri::test::MockRuntime> {
+        tauri::test::mock_builder()
+            .manage(AIServerState::new())
+            .build(tauri::test::mock_context(tauri::test::noop_assets()))
+            .expect("failed to build mock app")
+    }
+
+    /// No health server is listening on the port we hand it, and the process outlives
+    /// the timeout, so this should time out rather than hang.
+    #[tokio::test]
+    async fn wait_for_server_ready_times_out() {
+        let app = test_app();
+        let handle = app.handle().clone();
+        let port = allocate_free_port().expect("failed to allocate a port");
+
+        let mut child = Command::new("sh")
+            .arg("-c")
+            .arg("sleep 5")
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .expect("failed to spawn test process");
+
+        let result = wait_for_server_ready(
+            &mut child,
+            &handle,
+            port,
+            "test-secret",
+            Duration::from_millis(300),
+        )
+        .await;
+
+        let err = result.expect_err("expected a timeout error");
+        assert!(err.contains("timed out waiting for /api/health"), "{}", err);
+
+        let _ = child.kill().await;
+    }
+
+    /// If the sidecar exits during startup, the real crash reason (its stderr) should
+    /// be surfaced instead of a bare timeout.
+    #[tokio::test]
+    async fn wait_for_server_ready_surfaces_stderr_on_early_exit() {
+        let app = test_app();
+        let handle = app.handle().clone();
+        let port = allocate_free_port().expect("failed to allocate a port");
+
+        let mut child = Command::new("sh")
+            .arg("-c")
+            .arg("echo 'boom: missing model weights' 1>&2; exit 1")
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .expect("failed to spawn test process");
+
+        if let Some(stderr) = child.stderr.take() {
+            spawn_log_reader(handle.clone(), "stderr", stderr);
+        }
+        if let Some(stdout) = child.stdout.take() {
+            spawn_log_reader(handle.clone(), "stdout", stdout);
+        }
+
+        let result = wait_for_server_ready(
+            &mut child,
+            &handle,
+            port,
+            "test-secret",
+            Duration::from_secs(5),
+        )
+        .await;
+
+        let err = result.expect_err("expected the process exit to be reported");
+        assert!(err.contains("sidecar exited during startup"), "{}", err);
+        assert!(err.contains("boom: missing model weights"), "{}", err);
     }
 }
@@ -13,7 +13,12 @@ pub fn run() {
       ai_server::start_ai_server,
       ai_server::stop_ai_server,
       ai_server::get_server_status,
+      ai_server::run_inference,
       ai_server::detect_vertebrae,
+      ai_server::queue_detection,
+      ai_server::get_queue_status,
+      ai_server::cancel_inference,
+      ai_server::get_server_logs,
     ])
     .setup(|app| {
       if cfg!(debug_assertions) {